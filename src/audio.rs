@@ -0,0 +1,117 @@
+//! Square-wave buzzer for the CHIP-8 sound timer.
+//!
+//! Kept isolated from the rest of the emulator so `App` only has to flip
+//! the beep on or off; it doesn't need to know anything about sample
+//! rates, channels, or `cpal` itself.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, StreamConfig};
+
+const FREQUENCY_HZ: f32 = 440.;
+const VOLUME: f32 = 0.2;
+
+/// Owns a live `cpal` output stream that plays a continuous square wave
+/// while active, and silence otherwise. The stream itself is never
+/// stopped or restarted; [`Beeper::set_active`] just flips an atomic
+/// flag the audio callback reads once per output buffer.
+pub struct Beeper {
+    active: Arc<AtomicBool>,
+    _stream: cpal::Stream,
+}
+
+impl Beeper {
+    /// Opens the default output device and starts the stream immediately
+    /// (silent until toggled on). Returns `None` if there's no usable
+    /// audio device, in which case the emulator simply runs without sound.
+    pub fn new() -> Option<Self> {
+        let host = cpal::default_host();
+        let device = host.default_output_device()?;
+        let config = device.default_output_config().ok()?;
+        let active = Arc::new(AtomicBool::new(false));
+
+        let stream = build_stream(&device, &config, active.clone())?;
+        stream.play().ok()?;
+
+        Some(Self { active, _stream: stream })
+    }
+
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+}
+
+/// Returns `None` (rather than erroring out the whole app) for any
+/// `SampleFormat` variant the device reports that isn't handled below,
+/// same as every other failure path in this module degrades to no sound.
+fn build_stream(
+    device: &cpal::Device,
+    config: &cpal::SupportedStreamConfig,
+    active: Arc<AtomicBool>,
+) -> Option<cpal::Stream> {
+    let channels = config.channels() as usize;
+    let sample_rate = config.sample_rate().0 as f32;
+    let stream_config: StreamConfig = config.clone().into();
+    let err_fn = |err| eprintln!("Audio stream error: {err}");
+
+    match config.sample_format() {
+        SampleFormat::F32 => {
+            let mut phase = 0f32;
+            device.build_output_stream(
+                &stream_config,
+                move |data: &mut [f32], _| write_square_wave(data, channels, sample_rate, &mut phase, &active),
+                err_fn,
+                None,
+            ).ok()
+        },
+        SampleFormat::I16 => {
+            let mut phase = 0f32;
+            device.build_output_stream(
+                &stream_config,
+                move |data: &mut [i16], _| write_square_wave(data, channels, sample_rate, &mut phase, &active),
+                err_fn,
+                None,
+            ).ok()
+        },
+        SampleFormat::U16 => {
+            let mut phase = 0f32;
+            device.build_output_stream(
+                &stream_config,
+                move |data: &mut [u16], _| write_square_wave(data, channels, sample_rate, &mut phase, &active),
+                err_fn,
+                None,
+            ).ok()
+        },
+        format => {
+            eprintln!("Unsupported sample format {format:?}, running without sound");
+            None
+        },
+    }
+}
+
+fn write_square_wave<T: cpal::Sample + cpal::FromSample<f32>>(
+    data: &mut [T],
+    channels: usize,
+    sample_rate: f32,
+    phase: &mut f32,
+    active: &AtomicBool,
+) {
+    let step = FREQUENCY_HZ / sample_rate;
+    let is_active = active.load(Ordering::Relaxed);
+
+    for frame in data.chunks_mut(channels) {
+        let value = if is_active {
+            if *phase < 0.5 { VOLUME } else { -VOLUME }
+        } else {
+            0.
+        };
+        *phase = (*phase + step) % 1.0;
+
+        let sample = T::from_sample(value);
+        for out in frame.iter_mut() {
+            *out = sample;
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::LinkedList;
 
+mod audio;
+
 use iced::futures::FutureExt;
+use iced::keyboard;
 use iced::theme::Button;
 use iced::time::{every, Duration};
 use iced::widget::canvas::Cache;
@@ -13,6 +16,7 @@ use iced::widget::{
     Canvas,
     column,
     button,
+    pick_list,
     row,
     scrollable,
     scrollable::{Alignment, Properties},
@@ -34,6 +38,8 @@ fn main() -> Result<(), iced::Error> {
 struct App {
     emulator: Option<Emulator>,
     logs: Vec<String>,
+    beeper: Option<audio::Beeper>,
+    quirks_preset: QuirksPreset,
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +48,37 @@ enum Message {
     ROMOpened(Option<(Vec<u8>, String)>),
     ClearLog,
     Tick,
+    TimerTick,
+    KeyPressed(u8),
+    KeyReleased(u8),
+    QuirksPresetSelected(QuirksPreset),
+    Pause,
+    Resume,
+    Step,
+    SaveState,
+    StateSaved,
+    LoadState,
+    StateOpened(Option<Vec<u8>>),
+}
+
+/// Maps a physical QWERTY key to the COSMAC VIP hex keypad it sits in the
+/// same position as:
+///
+/// ```text
+/// 1 2 3 4      1 2 3 C
+/// Q W E R  ->  4 5 6 D
+/// A S D F      7 8 9 E
+/// Z X C V      A 0 B F
+/// ```
+fn map_key(key_code: keyboard::KeyCode) -> Option<u8> {
+    use keyboard::KeyCode;
+    match key_code {
+        KeyCode::Key1 => Some(0x1), KeyCode::Key2 => Some(0x2), KeyCode::Key3 => Some(0x3), KeyCode::Key4 => Some(0xC),
+        KeyCode::Q => Some(0x4), KeyCode::W => Some(0x5), KeyCode::E => Some(0x6), KeyCode::R => Some(0xD),
+        KeyCode::A => Some(0x7), KeyCode::S => Some(0x8), KeyCode::D => Some(0x9), KeyCode::F => Some(0xE),
+        KeyCode::Z => Some(0xA), KeyCode::X => Some(0x0), KeyCode::C => Some(0xB), KeyCode::V => Some(0xF),
+        _ => None,
+    }
 }
 
 impl Application for App {
@@ -51,7 +88,7 @@ impl Application for App {
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Message>) {
-        (Default::default(), Command::none())
+        (Self { beeper: audio::Beeper::new(), ..Default::default() }, Command::none())
     }
 
     fn title(&self) -> String {
@@ -74,10 +111,12 @@ impl Application for App {
             Message::ROMOpened(None) => self.logs.push("Dialog closed".to_owned()),
             Message::ROMOpened(Some((rom, filename))) => {
                 let rom_len = rom.len();
-                if let Some(emulator) = Emulator::new(rom) {
+                self.quirks_preset = QuirksPreset::guess_from_rom(&rom);
+                if let Some(emulator) = Emulator::new(rom, self.quirks_preset.quirks()) {
                     self.emulator = Some(emulator);
                     self.logs.clear();
                     self.logs.push(format!("ROM loaded: {filename}"));
+                    self.logs.push(format!("Guessed compatibility profile: {}", self.quirks_preset));
                     if rom_len & 1 > 0 {
                         self.logs.push(format!("Warning: ROM size {rom_len} is odd. This may cause undefined behaviors."))
                     }
@@ -89,6 +128,84 @@ impl Application for App {
             Message::Tick => {
                 self.emulator.as_mut().unwrap().tick(&mut self.logs)
             },
+            Message::TimerTick => {
+                let emulator = self.emulator.as_mut().unwrap();
+                emulator.tick_timers();
+                if let Some(beeper) = &self.beeper {
+                    beeper.set_active(emulator.sound_timer > 0);
+                }
+            },
+            Message::KeyPressed(key) => {
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.key_pressed(key);
+                }
+            },
+            Message::KeyReleased(key) => {
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.key_released(key);
+                }
+            },
+            Message::QuirksPresetSelected(preset) => {
+                self.quirks_preset = preset;
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.quirks = preset.quirks();
+                }
+            },
+            Message::Pause => {
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.paused = true;
+                }
+            },
+            Message::Resume => {
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.paused = false;
+                }
+            },
+            Message::Step => {
+                if let Some(emulator) = &mut self.emulator {
+                    emulator.tick(&mut self.logs);
+                }
+            },
+            Message::SaveState => {
+                if let Some(emulator) = &self.emulator {
+                    let bytes = emulator.save_state();
+                    let dialog = rfd::AsyncFileDialog::new()
+                        .set_title("Save state...")
+                        .add_filter("Chip-8 save state", &["c8state"])
+                        .save_file()
+                        .then(move |handle| {
+                            let bytes = bytes;
+                            async move {
+                                if let Some(handle) = handle {
+                                    let _ = handle.write(&bytes).await;
+                                }
+                            }
+                        });
+                    return Command::perform(dialog, |_| Message::StateSaved);
+                }
+            },
+            Message::StateSaved => self.logs.push("State saved".to_owned()),
+            Message::LoadState => {
+                let dialog = rfd::AsyncFileDialog::new()
+                    .set_title("Load state...")
+                    .add_filter("Chip-8 save state", &["c8state"])
+                    .pick_file()
+                    .then(|opt| async { match opt {
+                        Some(handle) => Some(handle.read().await),
+                        None => None,
+                    } });
+                return Command::perform(dialog, Message::StateOpened);
+            },
+            Message::StateOpened(None) => self.logs.push("Dialog closed".to_owned()),
+            Message::StateOpened(Some(bytes)) => {
+                match Emulator::from_state(&bytes, self.quirks_preset.quirks()) {
+                    Some(loaded) => {
+                        self.emulator = Some(loaded);
+                        self.logs.push("State loaded".to_owned());
+                    },
+                    None => self.logs.push("Error: invalid or corrupt save state".to_owned()),
+                }
+            },
         }
         Command::none()
     }
@@ -98,6 +215,9 @@ impl Application for App {
             row![
                 button("Load ROM...").on_press(Message::LoadROM),
                 button("Clear Logs").on_press_maybe(if self.logs.len() > 0 { Some(Message::ClearLog) } else { None } ).style(Button::Secondary),
+                pick_list(&QuirksPreset::ALL[..], Some(self.quirks_preset), Message::QuirksPresetSelected),
+                button("Save State...").on_press_maybe(self.emulator.is_some().then_some(Message::SaveState)),
+                button("Load State...").on_press(Message::LoadState),
             ].spacing(10)
         ).center_x().center_y().height(Length::Fill).width(Length::Fill);
 
@@ -114,6 +234,40 @@ impl Application for App {
             }).center_x().center_y()
         }.height(512.).width(Length::Fill);
 
+        let debug_panel: Container<'_, Message> = if let Some(emulator) = &self.emulator {
+            let mut lines = vec![
+                text(format!("PC: {:#06x}", emulator.pc)).into(),
+                text(format!("I:  {:#06x}", emulator.reg_i)).into(),
+            ];
+            for (i, v) in emulator.reg_v.iter().enumerate() {
+                lines.push(text(format!("V{i:X}: {v:#04x}")).into());
+            }
+            lines.push(text(format!("Stack depth: {}", emulator.stack.len())).into());
+            lines.push(text(format!("Delay timer: {}", emulator.delay_timer)).into());
+            lines.push(text(format!("Sound timer: {}", emulator.sound_timer)).into());
+            lines.push(text("Next instructions:").into());
+            for mnemonic in emulator.disassemble_ahead(5) {
+                lines.push(text(mnemonic).into());
+            }
+            lines.push(text("PC history:").into());
+            for pc in emulator.pc_history.iter().rev() {
+                lines.push(text(format!("{pc:#06x}")).into());
+            }
+
+            let controls = row![
+                if emulator.paused {
+                    button("Resume").on_press(Message::Resume)
+                } else {
+                    button("Pause").on_press(Message::Pause)
+                },
+                button("Step").on_press_maybe(emulator.paused.then_some(Message::Step)),
+            ].spacing(10);
+
+            container(column![scrollable(column(lines)).height(Length::Fill), controls].spacing(5))
+        } else {
+            container(text("Load a ROM to debug it."))
+        }.padding(5.).width(220.).height(Length::Fill);
+
         let logs: Container<'_, Message> = if self.logs.len() > 0 {
             container(
                 scrollable(column(self.logs.iter().map(|log| text(log).into()))).width(Length::Fill)
@@ -125,17 +279,29 @@ impl Application for App {
 
         container(column![
             top_bar,
-            middle_box,
+            row![middle_box, debug_panel],
             logs,
         ]).into()
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        if self.emulator.is_some() {
-            every(Duration::from_secs_f32(1. / 700.)).map(|_| Message::Tick)
-        } else {
-            Subscription::none()
+        let keys = iced::subscription::events_with(|event, _status| match event {
+            iced::Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => map_key(key_code).map(Message::KeyPressed),
+            iced::Event::Keyboard(keyboard::Event::KeyReleased { key_code, .. }) => map_key(key_code).map(Message::KeyReleased),
+            _ => None,
+        });
+
+        let Some(emulator) = &self.emulator else { return keys; };
+
+        // The CPU runs at 700 Hz, but the delay/sound timers always count
+        // down at a fixed 60 Hz, so they need their own independent
+        // subscription rather than being derived from the `Tick` rate.
+        // Only the CPU clock is paused by the debugger; timers keep going.
+        let mut subs = vec![keys, every(Duration::from_secs_f32(1. / 60.)).map(|_| Message::TimerTick)];
+        if !emulator.paused {
+            subs.push(every(Duration::from_secs_f32(1. / 700.)).map(|_| Message::Tick));
         }
+        Subscription::batch(subs)
     }
 }
 
@@ -146,11 +312,148 @@ struct Emulator {
     reg_i: u16,
     reg_v: [u8; 16],
     stack: LinkedList<u16>,
-    // TODO: implement more fields
+    delay_timer: u8,
+    sound_timer: u8,
+    keys: [bool; 16],
+    /// `Some(x)` while blocked on `FX0A`, waiting for a key to be pressed
+    /// and released into `VX`. Short-circuits `tick` entirely until resolved.
+    waiting_key_reg: Option<u8>,
+    rng: Xorshift32,
+    quirks: Quirks,
+    paused: bool,
+    pc_history: PcHistory,
+}
+
+const PC_HISTORY_LEN: usize = 32;
+
+/// A fixed-capacity ring buffer of the PC of each executed instruction,
+/// oldest overwritten first, for post-mortem inspection in the debugger.
+#[derive(Default)]
+struct PcHistory {
+    buf: [u16; PC_HISTORY_LEN],
+    next: usize,
+    len: usize,
+}
+
+impl PcHistory {
+    fn push(&mut self, pc: u16) {
+        self.buf[self.next] = pc;
+        self.next = (self.next + 1) % PC_HISTORY_LEN;
+        self.len = (self.len + 1).min(PC_HISTORY_LEN);
+    }
+
+    /// Oldest-to-newest iterator over the recorded PCs.
+    fn iter(&self) -> impl DoubleEndedIterator<Item = u16> + '_ {
+        let start = if self.len < PC_HISTORY_LEN { 0 } else { self.next };
+        (0..self.len).map(move |i| self.buf[(start + i) % PC_HISTORY_LEN])
+    }
+}
+
+/// Toggles for the handful of instructions whose behavior differs across
+/// CHIP-8 interpreters. The execute `match` in `tick` reads these instead of
+/// hardcoding one interpretation, so the same binary can run ROMs that
+/// assume contradictory semantics.
+#[derive(Debug, Clone, Copy)]
+struct Quirks {
+    /// Whether `8XY1`/`8XY2`/`8XY3` zero `VF` afterward.
+    vf_reset: bool,
+    /// Whether `FX55`/`FX65` leave `I` incremented by `X + 1`.
+    memory_increment: bool,
+    /// Whether `8XY6`/`8XYE` read `VY` into `VX` before shifting, rather
+    /// than shifting `VX` in place.
+    shift_uses_vy: bool,
+    /// Whether `BNNN` is actually `BXNN` (jump to `XNN + VX`).
+    jump_with_vx: bool,
+    /// Whether sprites are clipped at the screen edge rather than
+    /// wrapping around to the opposite side.
+    display_clipping: bool,
+}
+
+/// A named bundle of [`Quirks`], selectable from the top bar's dropdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum QuirksPreset {
+    #[default]
+    Chip8,
+    SuperChip,
+    XoChip,
+}
+
+impl QuirksPreset {
+    const ALL: [QuirksPreset; 3] = [QuirksPreset::Chip8, QuirksPreset::SuperChip, QuirksPreset::XoChip];
+
+    fn quirks(self) -> Quirks {
+        match self {
+            QuirksPreset::Chip8 => Quirks {
+                vf_reset: true,
+                memory_increment: true,
+                shift_uses_vy: true,
+                jump_with_vx: false,
+                display_clipping: true,
+            },
+            QuirksPreset::SuperChip => Quirks {
+                vf_reset: false,
+                memory_increment: false,
+                shift_uses_vy: false,
+                jump_with_vx: true,
+                display_clipping: true,
+            },
+            QuirksPreset::XoChip => Quirks {
+                vf_reset: false,
+                memory_increment: true,
+                shift_uses_vy: false,
+                jump_with_vx: false,
+                display_clipping: false,
+            },
+        }
+    }
+
+    /// A rough guess at the right profile from the ROM bytes alone: a
+    /// `00FF` (enable hires) or `00Cn` (scroll down) opcode is a strong
+    /// signal the ROM expects SUPER-CHIP semantics. Falls back to the
+    /// original CHIP-8 profile otherwise.
+    fn guess_from_rom(rom: &[u8]) -> Self {
+        let looks_like_schip = rom.chunks_exact(2).any(|op| op[0] == 0x00 && (op[1] == 0xFF || op[1] & 0xF0 == 0xC0));
+        if looks_like_schip {
+            QuirksPreset::SuperChip
+        } else {
+            QuirksPreset::Chip8
+        }
+    }
+}
+
+impl std::fmt::Display for QuirksPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            QuirksPreset::Chip8 => "CHIP-8",
+            QuirksPreset::SuperChip => "SUPER-CHIP",
+            QuirksPreset::XoChip => "XO-CHIP",
+        })
+    }
+}
+
+/// A tiny xorshift32 PRNG backing `CXNN`, so the core stays
+/// dependency-light and runs deterministically for replay/testing.
+struct Xorshift32 {
+    state: u32,
+}
+
+impl Xorshift32 {
+    fn new(seed: u32) -> Self {
+        Self { state: if seed == 0 { 1 } else { seed } }
+    }
+
+    fn next_u8(&mut self) -> u8 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x as u8
+    }
 }
 
 impl Emulator {
-    fn new(rom: Vec<u8>) -> Option<Self> {
+    fn new(rom: Vec<u8>, quirks: Quirks) -> Option<Self> {
         let mut memory = [0; 4096];
 
         let Some(prog_mem) = memory.get_mut(0x200..(0x200 + rom.len())) else { return None; };
@@ -169,138 +472,212 @@ impl Emulator {
             reg_i: 0,
             reg_v: [0; 16],
             stack: LinkedList::new(),
+            delay_timer: 0,
+            sound_timer: 0,
+            keys: [false; 16],
+            waiting_key_reg: None,
+            rng: Xorshift32::new(0xDEAD_BEEF),
+            quirks,
+            paused: false,
+            pc_history: PcHistory::default(),
         })
     }
 
+    fn key_pressed(&mut self, key: u8) {
+        self.keys[key as usize] = true;
+    }
+
+    fn key_released(&mut self, key: u8) {
+        self.keys[key as usize] = false;
+        if let Some(x) = self.waiting_key_reg {
+            self.reg_v[x as usize] = key;
+            self.waiting_key_reg = None;
+        }
+    }
+
+    /// Counts the delay and sound timers down by one, floored at zero.
+    /// Called from a dedicated 60 Hz subscription, independent of `tick`.
+    fn tick_timers(&mut self) {
+        self.delay_timer = self.delay_timer.saturating_sub(1);
+        self.sound_timer = self.sound_timer.saturating_sub(1);
+    }
+
     fn tick(&mut self, logs: &mut Vec<String>) {
+        if self.waiting_key_reg.is_some() {
+            // FX0A is blocking: halt fetch/decode entirely (the 60 Hz
+            // timers keep counting down via `tick_timers`, which runs
+            // off a separate subscription).
+            return;
+        }
+
+        self.pc_history.push(self.pc);
+
         let [first, nn, ..] = &self.memory[(self.pc as usize)..] else { unreachable!(); };
         let (first, nn) = (*first, *nn);
-
-        let opcat = (first & 0xF0) >> 4;
-        let x = first & 0xF;
-        let y = (nn & 0xF0) >> 4;
-        let n = nn & 0xF;
-        let nnn = ((x as u16) << 8) + nn as u16;
+        let opcode = decode(first, nn);
 
         self.pc += 2;
 
-        match opcat {
-            // 00E0
-            0 if nnn == 0x0E0 => {
+        match opcode {
+            Opcode::ClearScreen => {
                 self.screen.clear();
             },
-            // 00EE
-            0 if nnn == 0x0EE => {
+            Opcode::ScrollDown(n) => {
+                self.screen.scroll_down(n as usize);
+            },
+            Opcode::ScrollRight => {
+                self.screen.scroll_right(4);
+            },
+            Opcode::ScrollLeft => {
+                self.screen.scroll_left(4);
+            },
+            Opcode::Halt => {
+                self.paused = true;
+            },
+            Opcode::LoresMode => {
+                self.screen.set_hires(false);
+            },
+            Opcode::HiresMode => {
+                self.screen.set_hires(true);
+            },
+            Opcode::Return => {
                 if let Some(pc) = self.stack.pop_back() {
                     self.pc = pc;
                 } else {
                     logs.push("Warning: attempted to return while stack is empty.".into());
                 }
-            }
-            // 1NNN
-            1 => {
+            },
+            Opcode::Jump(nnn) => {
                 self.pc = nnn;
             },
-            // 2NNN
-            2 => {
+            Opcode::Call(nnn) => {
                 self.stack.push_back(self.pc);
                 self.pc = nnn;
             },
-            // 3XNN
-            3 => {
+            Opcode::SkipEqImm(x, nn) => {
                 if self.reg_v[x as usize] == nn {
                     self.pc += 2;
                 }
             },
-            // 4XNN
-            4 => {
+            Opcode::SkipNeqImm(x, nn) => {
                 if self.reg_v[x as usize] != nn {
                     self.pc += 2;
                 }
             },
-            // 5XY0
-            5 if n == 0 => {
+            Opcode::SkipEqReg(x, y) => {
                 if self.reg_v[x as usize] == self.reg_v[y as usize] {
                     self.pc += 2;
                 }
             },
-            // 6XNN
-            6 => {
+            Opcode::SetImm(x, nn) => {
                 self.reg_v[x as usize] = nn;
             },
-            // 7XNN
-            7 => {
+            Opcode::AddImm(x, nn) => {
                 self.reg_v[x as usize] = self.reg_v[x as usize].wrapping_add(nn);
             },
-            // 8XY0
-            8 if n == 0 => {
+            Opcode::SetReg(x, y) => {
                 self.reg_v[x as usize] = self.reg_v[y as usize];
             },
-            // 8XY1
-            8 if n == 1 => {
+            Opcode::Or(x, y) => {
                 self.reg_v[x as usize] |= self.reg_v[y as usize];
+                if self.quirks.vf_reset { self.reg_v[0xF] = 0; }
             },
-            // 8XY2
-            8 if n == 2 => {
+            Opcode::And(x, y) => {
                 self.reg_v[x as usize] &= self.reg_v[y as usize];
+                if self.quirks.vf_reset { self.reg_v[0xF] = 0; }
             },
-            // 8XY3
-            8 if n == 3 => {
+            Opcode::Xor(x, y) => {
                 self.reg_v[x as usize] ^= self.reg_v[y as usize];
+                if self.quirks.vf_reset { self.reg_v[0xF] = 0; }
             },
-            // 8XY4
-            8 if n == 4 => {
+            Opcode::AddReg(x, y) => {
                 let (x_new, carry) = self.reg_v[x as usize].overflowing_add(self.reg_v[y as usize]);
                 self.reg_v[x as usize] = x_new;
                 self.reg_v[0xF] = carry.into();
             },
-            // 8XY5
-            8 if n == 5 => {
+            Opcode::SubReg(x, y) => {
                 let (x_new, carry) = self.reg_v[x as usize].overflowing_sub(self.reg_v[y as usize]);
                 self.reg_v[x as usize] = x_new;
                 self.reg_v[0xF] = (!carry).into();
             },
-            // 8XY6
-            8 if n == 6 => {
-                self.reg_v[x as usize] = self.reg_v[y as usize];
-                let vf = self.reg_v[x as usize] & 1;
-                self.reg_v[x as usize] = self.reg_v[x as usize] >> 1;
+            Opcode::ShiftRight(x, y) => {
+                let src = if self.quirks.shift_uses_vy { self.reg_v[y as usize] } else { self.reg_v[x as usize] };
+                let vf = src & 1;
+                self.reg_v[x as usize] = src >> 1;
                 self.reg_v[0xF] = vf;
             },
-            // 8XY7
-            8 if n == 7 => {
+            Opcode::SubnReg(x, y) => {
                 let (x_new, carry) = self.reg_v[y as usize].overflowing_sub(self.reg_v[x as usize]);
                 self.reg_v[x as usize] = x_new;
                 self.reg_v[0xF] = (!carry).into();
             },
-            // 8XYE
-            8 if n == 0xE => {
-                self.reg_v[x as usize] = self.reg_v[y as usize];
-                let vf = (self.reg_v[x as usize] > 0x80).into();
-                self.reg_v[x as usize] = self.reg_v[x as usize] << 1;
+            Opcode::ShiftLeft(x, y) => {
+                let src = if self.quirks.shift_uses_vy { self.reg_v[y as usize] } else { self.reg_v[x as usize] };
+                let vf = (src > 0x80).into();
+                self.reg_v[x as usize] = src << 1;
                 self.reg_v[0xF] = vf;
             },
-            // 9XY0
-            9 if n == 0 => {
+            Opcode::SkipNeqReg(x, y) => {
                 if self.reg_v[x as usize] != self.reg_v[y as usize] {
                     self.pc += 2;
                 }
             },
-            // ANNN
-            0xA => {
+            Opcode::SetIndex(nnn) => {
                 self.reg_i = nnn;
             },
-            // DXYN
-            0xD => {
+            Opcode::JumpOffset(nnn) => {
+                let x = ((nnn & 0xF00) >> 8) as u8;
+                let offset = if self.quirks.jump_with_vx { self.reg_v[x as usize] } else { self.reg_v[0] };
+                self.pc = nnn + offset as u16;
+            },
+            Opcode::Random(x, nn) => {
+                self.reg_v[x as usize] = self.rng.next_u8() & nn;
+            },
+            Opcode::SkipKeyPressed(x) => {
+                if self.keys[(self.reg_v[x as usize] & 0xF) as usize] {
+                    self.pc += 2;
+                }
+            },
+            Opcode::SkipKeyNotPressed(x) => {
+                if !self.keys[(self.reg_v[x as usize] & 0xF) as usize] {
+                    self.pc += 2;
+                }
+            },
+            Opcode::Draw(x, y, n) => {
                 let i_usize = self.reg_i as usize;
+                let wide = n == 0 && self.screen.hires;
+                let sprite_len = if wide { 32 } else { n as usize };
                 self.reg_v[0xF] = self.screen.draw_sprite(
-                    &self.memory[i_usize..(i_usize + n as usize)],
-                    self.reg_v[x as usize] & 63,
-                    self.reg_v[y as usize] & 31,
+                    &self.memory[i_usize..(i_usize + sprite_len)],
+                    self.reg_v[x as usize],
+                    self.reg_v[y as usize],
+                    self.quirks.display_clipping,
+                    wide,
                 ).into();
             },
-            // FX33
-            0xF if nn == 0x33 => {
+            Opcode::GetDelayTimer(x) => {
+                self.reg_v[x as usize] = self.delay_timer;
+            },
+            Opcode::WaitKey(x) => {
+                self.waiting_key_reg = Some(x);
+            },
+            Opcode::SetDelayTimer(x) => {
+                self.delay_timer = self.reg_v[x as usize];
+            },
+            Opcode::SetSoundTimer(x) => {
+                self.sound_timer = self.reg_v[x as usize];
+            },
+            Opcode::AddIndex(x) => {
+                let (new_i, overflow) = self.reg_i.overflowing_add(self.reg_v[x as usize] as u16);
+                self.reg_i = new_i & 0x0FFF;
+                if overflow || new_i > 0x0FFF {
+                    self.reg_v[0xF] = 1;
+                }
+            },
+            Opcode::FontChar(x) => {
+                self.reg_i = 0x50 + (self.reg_v[x as usize] & 0xF) as u16 * 5;
+            },
+            Opcode::StoreBcd(x) => {
                 let i_usize = self.reg_i as usize;
                 let units = self.reg_v[x as usize] % 10;
                 let rest = self.reg_v[x as usize] / 10;
@@ -310,59 +687,443 @@ impl Emulator {
                 self.memory[i_usize + 1] = tens;
                 self.memory[i_usize + 2] = units;
             },
-            // FX55
-            0xF if nn == 0x55 => {
+            Opcode::StoreRegs(x) => {
                 let i_usize = self.reg_i as usize;
                 self.memory[i_usize..=(i_usize + x as usize)].copy_from_slice(&self.reg_v[0..=x as usize]);
+                if self.quirks.memory_increment {
+                    self.reg_i += x as u16 + 1;
+                }
             },
-            // FX65
-            0xF if nn == 0x65 => {
+            Opcode::LoadRegs(x) => {
                 let i_usize = self.reg_i as usize;
                 self.reg_v[0..=x as usize].copy_from_slice(&self.memory[i_usize..=(i_usize + x as usize)]);
+                if self.quirks.memory_increment {
+                    self.reg_i += x as u16 + 1;
+                }
             },
-            _ => {
+            Opcode::Unknown(first, nn) => {
                 logs.push(format!("Unknown instruction {first:x?}{nn:x?}; skipping"));
-            }
+            },
         }
     }
+
+    /// Decodes the `count` instructions starting at the current `pc`,
+    /// without mutating any state, for the debugger's disassembly view.
+    fn disassemble_ahead(&self, count: usize) -> Vec<String> {
+        (0..count)
+            .filter_map(|i| {
+                let addr = self.pc as usize + i * 2;
+                self.memory.get(addr..addr + 2).map(|bytes| disassemble(bytes[0], bytes[1]))
+            })
+            .collect()
+    }
+
+    /// Serializes memory, registers, stack, screen, timers and keys into
+    /// the hand-rolled `.c8state`
+    /// binary format below, so save states stay as dependency-light as
+    /// `Xorshift32` rather than pulling in `serde`.
+    fn save_state(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SAVE_STATE_MAGIC);
+        out.push(SAVE_STATE_VERSION);
+        out.extend_from_slice(&self.memory);
+        out.extend_from_slice(&self.pc.to_le_bytes());
+        out.extend_from_slice(&self.reg_i.to_le_bytes());
+        out.extend_from_slice(&self.reg_v);
+
+        out.push(self.stack.len() as u8);
+        for frame in &self.stack {
+            out.extend_from_slice(&frame.to_le_bytes());
+        }
+
+        out.push(self.screen.hires as u8);
+        out.extend_from_slice(&(self.screen.content.len() as u32).to_le_bytes());
+        for word in &self.screen.content {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+
+        out.push(self.delay_timer);
+        out.push(self.sound_timer);
+        for &key in &self.keys {
+            out.push(key as u8);
+        }
+
+        out
+    }
+
+    /// Rebuilds an `Emulator` from bytes written by [`Self::save_state`].
+    /// Doesn't require an existing `Emulator` to load into: `quirks` is
+    /// supplied by the caller (the session's currently selected profile)
+    /// and `paused` always starts `false`, since neither is part of the
+    /// serialized machine state. A pending `FX0A` wait, the RNG state and
+    /// the PC history aren't saved either: they start fresh on load.
+    fn from_state(bytes: &[u8], quirks: Quirks) -> Option<Self> {
+        let mut pos = 0usize;
+        macro_rules! take {
+            ($n:expr) => {{
+                let slice = bytes.get(pos..pos + $n)?;
+                pos += $n;
+                slice
+            }};
+        }
+
+        if take!(4) != SAVE_STATE_MAGIC {
+            return None;
+        }
+        if take!(1)[0] != SAVE_STATE_VERSION {
+            return None;
+        }
+
+        let mut memory = [0u8; 4096];
+        memory.copy_from_slice(take!(4096));
+
+        let pc = u16::from_le_bytes(take!(2).try_into().ok()?);
+        let reg_i = u16::from_le_bytes(take!(2).try_into().ok()?);
+
+        let mut reg_v = [0u8; 16];
+        reg_v.copy_from_slice(take!(16));
+
+        let stack_len = take!(1)[0] as usize;
+        let mut stack = LinkedList::new();
+        for _ in 0..stack_len {
+            stack.push_back(u16::from_le_bytes(take!(2).try_into().ok()?));
+        }
+
+        let hires = take!(1)[0] != 0;
+        let word_count = u32::from_le_bytes(take!(4).try_into().ok()?) as usize;
+        if bytes.len() - pos < word_count * 8 {
+            return None;
+        }
+        let mut content = Vec::with_capacity(word_count);
+        for _ in 0..word_count {
+            content.push(u64::from_le_bytes(take!(8).try_into().ok()?));
+        }
+
+        let delay_timer = take!(1)[0];
+        let sound_timer = take!(1)[0];
+
+        let mut keys = [false; 16];
+        for key in &mut keys {
+            *key = take!(1)[0] != 0;
+        }
+
+        // The `iced` canvas `Cache` isn't serializable, so it's simply
+        // rebuilt empty here, which forces the canvas to redraw.
+        let screen = Screen { hires, content, cache: Cache::default() };
+
+        Some(Self {
+            memory,
+            screen,
+            pc,
+            reg_i,
+            reg_v,
+            stack,
+            delay_timer,
+            sound_timer,
+            keys,
+            waiting_key_reg: None,
+            rng: Xorshift32::new(0xDEAD_BEEF),
+            quirks,
+            paused: false,
+            pc_history: PcHistory::default(),
+        })
+    }
 }
 
-#[derive(Default)]
+const SAVE_STATE_MAGIC: &[u8; 4] = b"C8ST";
+const SAVE_STATE_VERSION: u8 = 1;
+
+/// A decoded CHIP-8 instruction, kept separate from execution so the
+/// debugger's disassembly view can decode bytes without mutating any
+/// `Emulator` state.
+#[derive(Debug, Clone, Copy)]
+enum Opcode {
+    ClearScreen,
+    /// `00Cn`: scroll the screen down by `n` rows.
+    ScrollDown(u8),
+    /// `00FB`: scroll the screen right by 4 pixels.
+    ScrollRight,
+    /// `00FC`: scroll the screen left by 4 pixels.
+    ScrollLeft,
+    /// `00FD`: halt the interpreter.
+    Halt,
+    /// `00FE`: switch to the 64x32 lores display.
+    LoresMode,
+    /// `00FF`: switch to the 128x64 hires display.
+    HiresMode,
+    Return,
+    Jump(u16),
+    Call(u16),
+    SkipEqImm(u8, u8),
+    SkipNeqImm(u8, u8),
+    SkipEqReg(u8, u8),
+    SetImm(u8, u8),
+    AddImm(u8, u8),
+    SetReg(u8, u8),
+    Or(u8, u8),
+    And(u8, u8),
+    Xor(u8, u8),
+    AddReg(u8, u8),
+    SubReg(u8, u8),
+    ShiftRight(u8, u8),
+    SubnReg(u8, u8),
+    ShiftLeft(u8, u8),
+    SkipNeqReg(u8, u8),
+    SetIndex(u16),
+    JumpOffset(u16),
+    Random(u8, u8),
+    Draw(u8, u8, u8),
+    SkipKeyPressed(u8),
+    SkipKeyNotPressed(u8),
+    GetDelayTimer(u8),
+    WaitKey(u8),
+    SetDelayTimer(u8),
+    SetSoundTimer(u8),
+    AddIndex(u8),
+    FontChar(u8),
+    StoreBcd(u8),
+    StoreRegs(u8),
+    LoadRegs(u8),
+    Unknown(u8, u8),
+}
+
+fn decode(first: u8, nn: u8) -> Opcode {
+    let opcat = (first & 0xF0) >> 4;
+    let x = first & 0xF;
+    let y = (nn & 0xF0) >> 4;
+    let n = nn & 0xF;
+    let nnn = ((x as u16) << 8) + nn as u16;
+
+    match opcat {
+        0 if nnn == 0x0E0 => Opcode::ClearScreen,
+        0 if nnn == 0x0EE => Opcode::Return,
+        0 if nn & 0xF0 == 0xC0 => Opcode::ScrollDown(n),
+        0 if nn == 0xFB => Opcode::ScrollRight,
+        0 if nn == 0xFC => Opcode::ScrollLeft,
+        0 if nn == 0xFD => Opcode::Halt,
+        0 if nn == 0xFE => Opcode::LoresMode,
+        0 if nn == 0xFF => Opcode::HiresMode,
+        1 => Opcode::Jump(nnn),
+        2 => Opcode::Call(nnn),
+        3 => Opcode::SkipEqImm(x, nn),
+        4 => Opcode::SkipNeqImm(x, nn),
+        5 if n == 0 => Opcode::SkipEqReg(x, y),
+        6 => Opcode::SetImm(x, nn),
+        7 => Opcode::AddImm(x, nn),
+        8 if n == 0 => Opcode::SetReg(x, y),
+        8 if n == 1 => Opcode::Or(x, y),
+        8 if n == 2 => Opcode::And(x, y),
+        8 if n == 3 => Opcode::Xor(x, y),
+        8 if n == 4 => Opcode::AddReg(x, y),
+        8 if n == 5 => Opcode::SubReg(x, y),
+        8 if n == 6 => Opcode::ShiftRight(x, y),
+        8 if n == 7 => Opcode::SubnReg(x, y),
+        8 if n == 0xE => Opcode::ShiftLeft(x, y),
+        9 if n == 0 => Opcode::SkipNeqReg(x, y),
+        0xA => Opcode::SetIndex(nnn),
+        0xB => Opcode::JumpOffset(nnn),
+        0xC => Opcode::Random(x, nn),
+        0xD => Opcode::Draw(x, y, n),
+        0xE if nn == 0x9E => Opcode::SkipKeyPressed(x),
+        0xE if nn == 0xA1 => Opcode::SkipKeyNotPressed(x),
+        0xF if nn == 0x07 => Opcode::GetDelayTimer(x),
+        0xF if nn == 0x0A => Opcode::WaitKey(x),
+        0xF if nn == 0x15 => Opcode::SetDelayTimer(x),
+        0xF if nn == 0x18 => Opcode::SetSoundTimer(x),
+        0xF if nn == 0x1E => Opcode::AddIndex(x),
+        0xF if nn == 0x29 => Opcode::FontChar(x),
+        0xF if nn == 0x33 => Opcode::StoreBcd(x),
+        0xF if nn == 0x55 => Opcode::StoreRegs(x),
+        0xF if nn == 0x65 => Opcode::LoadRegs(x),
+        _ => Opcode::Unknown(first, nn),
+    }
+}
+
+/// Formats a decoded opcode as a short human-readable mnemonic, for the
+/// debugger panel.
+fn disassemble(first: u8, nn: u8) -> String {
+    match decode(first, nn) {
+        Opcode::ClearScreen => "CLS".into(),
+        Opcode::ScrollDown(n) => format!("SCD {n:X}"),
+        Opcode::ScrollRight => "SCR".into(),
+        Opcode::ScrollLeft => "SCL".into(),
+        Opcode::Halt => "EXIT".into(),
+        Opcode::LoresMode => "LOW".into(),
+        Opcode::HiresMode => "HIGH".into(),
+        Opcode::Return => "RET".into(),
+        Opcode::Jump(nnn) => format!("JP {nnn:#05x}"),
+        Opcode::Call(nnn) => format!("CALL {nnn:#05x}"),
+        Opcode::SkipEqImm(x, nn) => format!("SE V{x:X}, {nn:#04x}"),
+        Opcode::SkipNeqImm(x, nn) => format!("SNE V{x:X}, {nn:#04x}"),
+        Opcode::SkipEqReg(x, y) => format!("SE V{x:X}, V{y:X}"),
+        Opcode::SetImm(x, nn) => format!("LD V{x:X}, {nn:#04x}"),
+        Opcode::AddImm(x, nn) => format!("ADD V{x:X}, {nn:#04x}"),
+        Opcode::SetReg(x, y) => format!("LD V{x:X}, V{y:X}"),
+        Opcode::Or(x, y) => format!("OR V{x:X}, V{y:X}"),
+        Opcode::And(x, y) => format!("AND V{x:X}, V{y:X}"),
+        Opcode::Xor(x, y) => format!("XOR V{x:X}, V{y:X}"),
+        Opcode::AddReg(x, y) => format!("ADD V{x:X}, V{y:X}"),
+        Opcode::SubReg(x, y) => format!("SUB V{x:X}, V{y:X}"),
+        Opcode::ShiftRight(x, y) => format!("SHR V{x:X}, V{y:X}"),
+        Opcode::SubnReg(x, y) => format!("SUBN V{x:X}, V{y:X}"),
+        Opcode::ShiftLeft(x, y) => format!("SHL V{x:X}, V{y:X}"),
+        Opcode::SkipNeqReg(x, y) => format!("SNE V{x:X}, V{y:X}"),
+        Opcode::SetIndex(nnn) => format!("LD I, {nnn:#05x}"),
+        Opcode::JumpOffset(nnn) => format!("JP V0, {nnn:#05x}"),
+        Opcode::Random(x, nn) => format!("RND V{x:X}, {nn:#04x}"),
+        Opcode::Draw(x, y, n) => format!("DRW V{x:X}, V{y:X}, {n:X}"),
+        Opcode::SkipKeyPressed(x) => format!("SKP V{x:X}"),
+        Opcode::SkipKeyNotPressed(x) => format!("SKNP V{x:X}"),
+        Opcode::GetDelayTimer(x) => format!("LD V{x:X}, DT"),
+        Opcode::WaitKey(x) => format!("LD V{x:X}, K"),
+        Opcode::SetDelayTimer(x) => format!("LD DT, V{x:X}"),
+        Opcode::SetSoundTimer(x) => format!("LD ST, V{x:X}"),
+        Opcode::AddIndex(x) => format!("ADD I, V{x:X}"),
+        Opcode::FontChar(x) => format!("LD F, V{x:X}"),
+        Opcode::StoreBcd(x) => format!("LD B, V{x:X}"),
+        Opcode::StoreRegs(x) => format!("LD [I], V0..V{x:X}"),
+        Opcode::LoadRegs(x) => format!("LD V0..V{x:X}, [I]"),
+        Opcode::Unknown(first, nn) => format!("??? {first:02X}{nn:02X}"),
+    }
+}
+
+const LORES_WIDTH: usize = 64;
+const LORES_HEIGHT: usize = 32;
+const HIRES_WIDTH: usize = 128;
+const HIRES_HEIGHT: usize = 64;
+
+/// The CHIP-8/SUPER-CHIP framebuffer. Lores (64x32) and hires (128x64)
+/// share the same bit-packed representation: each row is one or two
+/// `u64` words, MSB-first, so column 0 is the MSB of the row's first word.
 struct Screen {
-    content: [u64; 32],
+    hires: bool,
+    content: Vec<u64>,
     cache: Cache,
 }
 
+impl Default for Screen {
+    fn default() -> Self {
+        let mut screen = Self { hires: false, content: Vec::new(), cache: Cache::default() };
+        screen.clear();
+        screen
+    }
+}
+
 impl Screen {
+    fn width(&self) -> usize {
+        if self.hires { HIRES_WIDTH } else { LORES_WIDTH }
+    }
+
+    fn height(&self) -> usize {
+        if self.hires { HIRES_HEIGHT } else { LORES_HEIGHT }
+    }
+
+    fn words_per_row(&self) -> usize {
+        self.width() / 64
+    }
+
     fn clear(&mut self) {
         self.cache.clear();
-        self.content = [0; 32];
+        self.content = vec![0; self.words_per_row() * self.height()];
+    }
+
+    /// Switches between the 64x32 and 128x64 framebuffers (`00FE`/`00FF`),
+    /// clearing the screen as real SUPER-CHIP interpreters do.
+    fn set_hires(&mut self, hires: bool) {
+        self.hires = hires;
+        self.clear();
+    }
+
+    /// `00Cn`: scrolls the whole picture down by `rows`, leaving blank
+    /// rows at the top.
+    fn scroll_down(&mut self, rows: usize) {
+        self.cache.clear();
+        let words = self.words_per_row();
+        let height = self.height();
+        let rows = rows.min(height);
+        self.content.copy_within(0..(height - rows) * words, rows * words);
+        self.content[..rows * words].fill(0);
+    }
+
+    /// `00FB`/`00FC`: scrolls every row sideways by `pixels`, treating
+    /// each row as a single wide integer so the shift carries across the
+    /// word boundary in hires mode.
+    fn scroll_right(&mut self, pixels: u32) {
+        self.scroll_horizontal(pixels, true);
+    }
+
+    fn scroll_left(&mut self, pixels: u32) {
+        self.scroll_horizontal(pixels, false);
+    }
+
+    fn scroll_horizontal(&mut self, pixels: u32, right: bool) {
+        self.cache.clear();
+        let words = self.words_per_row();
+        for row in self.content.chunks_mut(words) {
+            if words == 1 {
+                row[0] = if right { row[0] >> pixels } else { row[0] << pixels };
+            } else {
+                let combined = ((row[0] as u128) << 64) | row[1] as u128;
+                let shifted = if right { combined >> pixels } else { combined << pixels };
+                row[0] = (shifted >> 64) as u64;
+                row[1] = shifted as u64;
+            }
+        }
     }
 
-    fn draw_sprite(&mut self, sprite: &[u8], x: u8, y: u8) -> bool {
+    /// Draws `sprite` at `(x, y)`, XORing it into the framebuffer.
+    /// `wide` selects the SUPER-CHIP 16x16 sprite format (two bytes per
+    /// row) used by `DXY0` in hires mode; otherwise each row is a single
+    /// byte, 8 pixels wide. `clip_display` governs both edges the same
+    /// way: rows/columns that would fall past the bottom/right edge are
+    /// dropped when set (the original CHIP-8 behavior), or wrap around to
+    /// the opposite edge of the screen when unset.
+    fn draw_sprite(&mut self, sprite: &[u8], x: u8, y: u8, clip_display: bool, wide: bool) -> bool {
         self.cache.clear();
+
+        let width = self.width();
+        let height = self.height();
+        let words = self.words_per_row();
+        let sprite_width: u32 = if wide { 16 } else { 8 };
+        let bytes_per_row = if wide { 2 } else { 1 };
+        let sprite_rows = sprite.len() / bytes_per_row;
+
+        let x = x as usize % width;
+        let y = y as usize % height;
+        let row_count = if clip_display { sprite_rows.min(height - y) } else { sprite_rows };
+
         let mut vf_ret = false;
-        let bitshift_count: i8 = 56 - x as i8;
-        match bitshift_count {
-            x if x > 0 => for row in 0..(sprite.len().min(32 - y as usize)) {
-                let shifted: u64 = (sprite[row] as u64) << x;
-                let target = &mut self.content[y as usize + row];
-                if *target & shifted > 0 { vf_ret = true; }
-                *target = *target ^ shifted;
-            },
-            x if x < 0 => for row in 0..(sprite.len().min(32 - y as usize)) {
-                let shifted: u64 = sprite[row] as u64 >> x;
-                let target = &mut self.content[y as usize + row];
-                if *target & shifted > 0 { vf_ret = true; }
-                *target = *target ^ shifted;
-            },
-            _ => for row in 0..(sprite.len().min(32 - y as usize)) {
-                let draw_target: u64 = sprite[row].into();
-                let target = &mut self.content[y as usize + row];
-                if *target & draw_target > 0 { vf_ret = true; }
-                *target = *target ^ draw_target;
-            },
+        for row in 0..row_count {
+            // Left-aligned in the top `sprite_width` bits so the same mask
+            // walk below works for both the 8-wide and 16-wide formats.
+            let sprite_row: u16 = if wide {
+                ((sprite[row * 2] as u16) << 8) | sprite[row * 2 + 1] as u16
+            } else {
+                (sprite[row] as u16) << 8
+            };
+
+            let target_y = if clip_display { y + row } else { (y + row) % height };
+
+            for bit in 0..sprite_width {
+                if sprite_row & (0x8000 >> bit) == 0 {
+                    continue;
+                }
+
+                let raw_x = x + bit as usize;
+                if raw_x >= width && clip_display {
+                    continue;
+                }
+                let target_x = raw_x % width;
+
+                let idx = target_y * words + target_x / 64;
+                let bit_in_word = 63 - (target_x % 64);
+                let mask = 1u64 << bit_in_word;
+
+                if self.content[idx] & mask != 0 { vf_ret = true; }
+                self.content[idx] ^= mask;
+            }
         }
+
         vf_ret
     }
 }
@@ -380,19 +1141,22 @@ impl Program<Message> for Emulator {
         bounds: iced::Rectangle,
         _cursor: iced::mouse::Cursor,
     ) -> Vec<iced::widget::canvas::Geometry> {
-        let pixels = self.screen.cache.draw(renderer, bounds.size(), |frame| {
-            for (i, row) in self.screen.content.iter().enumerate() {
-                let y = (i as f32) * PIXEL_SIZE;
-                let mut row = *row;
+        let screen = &self.screen;
+        // SUPER-CHIP's 128x64 hires screen packs twice the pixels into
+        // the same `middle_box` area as the 64x32 lores screen.
+        let pixel_size = if screen.hires { PIXEL_SIZE / 2. } else { PIXEL_SIZE };
+        let words = screen.words_per_row();
 
-                for x in (0..64).rev().map(|x| (x as f32) * PIXEL_SIZE) {
+        let pixels = screen.cache.draw(renderer, bounds.size(), |frame| {
+            for y in 0..screen.height() {
+                let row = &screen.content[y * words..(y + 1) * words];
+                for x in 0..screen.width() {
+                    let on = (row[x / 64] >> (63 - x % 64)) & 1 != 0;
                     frame.fill_rectangle(
-                        Point { x, y },
-                        Size { width: PIXEL_SIZE, height: PIXEL_SIZE },
-                        if row & 1 > 0 { Color::WHITE } else { Color::BLACK },
+                        Point { x: x as f32 * pixel_size, y: y as f32 * pixel_size },
+                        Size { width: pixel_size, height: pixel_size },
+                        if on { Color::WHITE } else { Color::BLACK },
                     );
-
-                    row = row >> 1;
                 }
             }
         });
@@ -418,4 +1182,151 @@ const FONT: [u8; 80] = [
     0xE0, 0x90, 0x90, 0x90, 0xE0, // D
     0xF0, 0x80, 0xF0, 0x80, 0xF0, // E
     0xF0, 0x80, 0xF0, 0x80, 0x80  // F
-];
\ No newline at end of file
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_state_round_trips_through_from_state() {
+        let mut emulator = Emulator::new(vec![0x12, 0x34], QuirksPreset::Chip8.quirks()).unwrap();
+        emulator.pc = 0x300;
+        emulator.reg_i = 0x123;
+        emulator.reg_v[5] = 42;
+        emulator.stack.push_back(0x400);
+        emulator.delay_timer = 10;
+        emulator.sound_timer = 20;
+        emulator.keys[3] = true;
+        emulator.screen.draw_sprite(&[0xFF], 0, 0, true, false);
+
+        let bytes = emulator.save_state();
+        let loaded = Emulator::from_state(&bytes, QuirksPreset::SuperChip.quirks()).unwrap();
+
+        assert_eq!(loaded.memory, emulator.memory);
+        assert_eq!(loaded.pc, emulator.pc);
+        assert_eq!(loaded.reg_i, emulator.reg_i);
+        assert_eq!(loaded.reg_v, emulator.reg_v);
+        assert_eq!(loaded.stack, emulator.stack);
+        assert_eq!(loaded.delay_timer, emulator.delay_timer);
+        assert_eq!(loaded.sound_timer, emulator.sound_timer);
+        assert_eq!(loaded.keys, emulator.keys);
+        assert_eq!(loaded.screen.hires, emulator.screen.hires);
+        assert_eq!(loaded.screen.content, emulator.screen.content);
+
+        // `from_state` takes its quirks from the caller, not the file.
+        assert_eq!(loaded.quirks.display_clipping, QuirksPreset::SuperChip.quirks().display_clipping);
+        assert!(!loaded.paused);
+    }
+
+    #[test]
+    fn from_state_rejects_truncated_bytes() {
+        let emulator = Emulator::new(vec![0x12, 0x34], QuirksPreset::Chip8.quirks()).unwrap();
+        let mut bytes = emulator.save_state();
+        bytes.truncate(bytes.len() - 1);
+
+        assert!(Emulator::from_state(&bytes, QuirksPreset::Chip8.quirks()).is_none());
+    }
+
+    #[test]
+    fn from_state_rejects_corrupt_word_count_without_allocating() {
+        let emulator = Emulator::new(vec![0x12, 0x34], QuirksPreset::Chip8.quirks()).unwrap();
+        let mut bytes = emulator.save_state();
+
+        // The 4-byte word count field sits right after the 1-byte `hires`
+        // flag, which itself follows the fixed-size header + stack.
+        let word_count_pos = 4 + 1 + 4096 + 2 + 2 + 16 + 1 + 1;
+        bytes[word_count_pos..word_count_pos + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes.truncate(word_count_pos + 4);
+
+        assert!(Emulator::from_state(&bytes, QuirksPreset::Chip8.quirks()).is_none());
+    }
+
+    /// Mirrors the bit convention `Program::draw` reads: column `x` lives
+    /// at bit `63 - x % 64` of word `x / 64` in the row.
+    fn pixel_at(screen: &Screen, x: usize, y: usize) -> bool {
+        let words = screen.words_per_row();
+        let row = &screen.content[y * words..(y + 1) * words];
+        (row[x / 64] >> (63 - x % 64)) & 1 != 0
+    }
+
+    #[test]
+    fn draw_sprite_reports_collision_and_xors_pixels_off() {
+        let mut screen = Screen::default();
+
+        let vf = screen.draw_sprite(&[0xFF], 0, 0, true, false);
+        assert!(!vf);
+        for x in 0..8 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+
+        // Drawing the same sprite again XORs the same pixels back off and
+        // must report the collision.
+        let vf = screen.draw_sprite(&[0xFF], 0, 0, true, false);
+        assert!(vf);
+        for x in 0..8 {
+            assert!(!pixel_at(&screen, x, 0));
+        }
+    }
+
+    #[test]
+    fn draw_sprite_wraps_horizontally_when_clip_display_is_off() {
+        let mut screen = Screen::default();
+
+        screen.draw_sprite(&[0xFF], 60, 0, false, false);
+        for x in 60..64 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+        for x in 0..4 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+    }
+
+    #[test]
+    fn draw_sprite_clips_horizontally_when_clip_display_is_on() {
+        let mut screen = Screen::default();
+
+        screen.draw_sprite(&[0xFF], 60, 0, true, false);
+        for x in 60..64 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+        for x in 0..4 {
+            assert!(!pixel_at(&screen, x, 0));
+        }
+    }
+
+    #[test]
+    fn draw_sprite_wraps_vertically_when_clip_display_is_off() {
+        let mut screen = Screen::default();
+
+        screen.draw_sprite(&[0xFF, 0xFF, 0xFF], 0, 31, false, false);
+        assert!(pixel_at(&screen, 0, 31));
+        assert!(pixel_at(&screen, 0, 0));
+        assert!(pixel_at(&screen, 0, 1));
+    }
+
+    #[test]
+    fn draw_sprite_clips_vertically_when_clip_display_is_on() {
+        let mut screen = Screen::default();
+
+        screen.draw_sprite(&[0xFF, 0xFF, 0xFF], 0, 31, true, false);
+        assert!(pixel_at(&screen, 0, 31));
+        assert!(!pixel_at(&screen, 0, 0));
+        assert!(!pixel_at(&screen, 0, 1));
+    }
+
+    #[test]
+    fn draw_sprite_wraps_wide_hires_sprite_horizontally() {
+        let mut screen = Screen::default();
+        screen.set_hires(true);
+
+        let vf = screen.draw_sprite(&[0xFF, 0xFF, 0xFF, 0xFF], 120, 0, false, true);
+        assert!(!vf);
+        for x in 120..128 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+        for x in 0..8 {
+            assert!(pixel_at(&screen, x, 0));
+        }
+    }
+}
\ No newline at end of file